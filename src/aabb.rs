@@ -0,0 +1,62 @@
+use std::ops::Range;
+
+use bevy::prelude::*;
+
+#[derive(Reflect, Default, Clone, Copy, Debug)]
+#[reflect(Default)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    pub fn surrounding(a: Aabb, b: Aabb) -> Aabb {
+        Aabb {
+            min: a.min.min(b.min),
+            max: a.max.max(b.max),
+        }
+    }
+
+    pub fn centroid(&self) -> Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    /// Widens any axis thinner than `EPSILON`, so a perfectly flat bounding box
+    /// (e.g. a `Quad` lying in a plane) still has a non-degenerate slab test.
+    pub fn padded(self) -> Aabb {
+        const EPSILON: f32 = 0.0001;
+        let pad = |min: f32, max: f32| {
+            if max - min < EPSILON {
+                (min - EPSILON * 0.5, max + EPSILON * 0.5)
+            } else {
+                (min, max)
+            }
+        };
+        let (min_x, max_x) = pad(self.min.x, self.max.x);
+        let (min_y, max_y) = pad(self.min.y, self.max.y);
+        let (min_z, max_z) = pad(self.min.z, self.max.z);
+        Aabb {
+            min: Vec3::new(min_x, min_y, min_z),
+            max: Vec3::new(max_x, max_y, max_z),
+        }
+    }
+
+    pub fn hit(&self, ray: &Ray, interval: Range<f32>) -> bool {
+        let mut lo = interval.start;
+        let mut hi = interval.end;
+        for axis in 0..3 {
+            let inv_d = 1.0 / ray.direction[axis];
+            let mut t0 = (self.min[axis] - ray.origin[axis]) * inv_d;
+            let mut t1 = (self.max[axis] - ray.origin[axis]) * inv_d;
+            if inv_d < 0.0 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+            lo = t0.max(lo);
+            hi = t1.min(hi);
+            if hi <= lo {
+                return false;
+            }
+        }
+        true
+    }
+}
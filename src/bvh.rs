@@ -0,0 +1,80 @@
+use std::ops::Range;
+
+use crate::aabb::Aabb;
+use crate::hittable::{HitRecord, Hittable};
+use crate::ray::TimedRay;
+use crate::scene::Shape;
+
+pub enum Bvh {
+    Leaf(usize, Aabb),
+    Branch {
+        left: Box<Bvh>,
+        right: Box<Bvh>,
+        bbox: Aabb,
+    },
+}
+
+impl Bvh {
+    pub fn build(shapes: &[Shape], indices: &[usize]) -> Option<Bvh> {
+        if indices.is_empty() {
+            return None;
+        }
+        if indices.len() == 1 {
+            let index = indices[0];
+            let bbox = shapes[index].bounding_box().expect("bvh indices are pre-filtered to bounded shapes");
+            return Some(Bvh::Leaf(index, bbox));
+        }
+
+        let boxes: Vec<Aabb> = indices
+            .iter()
+            .map(|&i| shapes[i].bounding_box().expect("bvh indices are pre-filtered to bounded shapes"))
+            .collect();
+        let extent = boxes
+            .iter()
+            .skip(1)
+            .fold(boxes[0], |acc, &b| Aabb::surrounding(acc, b));
+        let span = extent.max - extent.min;
+        let axis = if span.x >= span.y && span.x >= span.z {
+            0
+        } else if span.y >= span.z {
+            1
+        } else {
+            2
+        };
+
+        let mut sorted = indices.to_vec();
+        sorted.sort_by(|&a, &b| {
+            let ca = shapes[a].bounding_box().unwrap().centroid()[axis];
+            let cb = shapes[b].bounding_box().unwrap().centroid()[axis];
+            ca.partial_cmp(&cb).unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let mid = sorted.len() / 2;
+        let left = Box::new(Bvh::build(shapes, &sorted[..mid]).unwrap());
+        let right = Box::new(Bvh::build(shapes, &sorted[mid..]).unwrap());
+        let bbox = Aabb::surrounding(left.bbox(), right.bbox());
+        Some(Bvh::Branch { left, right, bbox })
+    }
+
+    pub fn bbox(&self) -> Aabb {
+        match self {
+            Bvh::Leaf(_, bbox) => *bbox,
+            Bvh::Branch { bbox, .. } => *bbox,
+        }
+    }
+
+    pub fn hit(&self, shapes: &[Shape], ray: &TimedRay, interval: Range<f32>) -> Option<HitRecord> {
+        if !self.bbox().hit(&ray.ray, interval.clone()) {
+            return None;
+        }
+        match self {
+            Bvh::Leaf(index, _) => shapes.get(*index).and_then(|shape| shape.hit(ray, interval)),
+            Bvh::Branch { left, right, .. } => {
+                let left_hit = left.hit(shapes, ray, interval.clone());
+                let closest = left_hit.as_ref().map_or(interval.end, |hit| hit.t);
+                let right_hit = right.hit(shapes, ray, interval.start..closest);
+                right_hit.or(left_hit)
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use std::ops::Range;
+
+use bevy::prelude::*;
+
+use crate::aabb::Aabb;
+use crate::ray::TimedRay;
+use crate::scene::Mat;
+
+pub trait Hittable {
+    fn hit(&self, ray: &TimedRay, interval: Range<f32>) -> Option<HitRecord>;
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        None
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct HitRecord {
+    pub point: Vec3,
+    pub normal: Vec3,
+    pub t: f32,
+    pub front_face: bool,
+    pub material: Mat,
+}
+
+impl<T: Hittable> Hittable for [T] {
+    fn hit(&self, ray: &TimedRay, interval: Range<f32>) -> Option<HitRecord> {
+        let mut closest = interval.end;
+        let mut result = None;
+        for object in self {
+            if let Some(hit) = object.hit(ray, interval.start..closest) {
+                closest = hit.t;
+                result = Some(hit);
+            }
+        }
+        result
+    }
+}
+
+impl<T: Hittable> Hittable for Vec<T> {
+    fn hit(&self, ray: &TimedRay, interval: Range<f32>) -> Option<HitRecord> {
+        self.as_slice().hit(ray, interval)
+    }
+}
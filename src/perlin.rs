@@ -0,0 +1,110 @@
+use std::sync::OnceLock;
+
+use bevy::prelude::Vec3;
+
+const POINT_COUNT: usize = 256;
+
+static PERLIN: OnceLock<Perlin> = OnceLock::new();
+
+/// The shared Perlin lattice, hashed once on first use and reused by every
+/// `Texture::Noise` sample.
+pub fn lattice() -> &'static Perlin {
+    PERLIN.get_or_init(Perlin::new)
+}
+
+pub struct Perlin {
+    random_vectors: [Vec3; POINT_COUNT],
+    perm_x: [usize; POINT_COUNT],
+    perm_y: [usize; POINT_COUNT],
+    perm_z: [usize; POINT_COUNT],
+}
+
+impl Perlin {
+    fn new() -> Self {
+        let mut random_vectors = [Vec3::ZERO; POINT_COUNT];
+        for vector in random_vectors.iter_mut() {
+            *vector = Vec3::new(
+                rand::random::<f32>() * 2.0 - 1.0,
+                rand::random::<f32>() * 2.0 - 1.0,
+                rand::random::<f32>() * 2.0 - 1.0,
+            )
+            .normalize();
+        }
+
+        Self {
+            random_vectors,
+            perm_x: Self::generate_permutation(),
+            perm_y: Self::generate_permutation(),
+            perm_z: Self::generate_permutation(),
+        }
+    }
+
+    fn generate_permutation() -> [usize; POINT_COUNT] {
+        let mut values = [0; POINT_COUNT];
+        for (index, value) in values.iter_mut().enumerate() {
+            *value = index;
+        }
+        for i in (1..POINT_COUNT).rev() {
+            let j = (rand::random::<f32>() * (i + 1) as f32) as usize;
+            values.swap(i, j);
+        }
+        values
+    }
+
+    /// Trilinearly-interpolated gradient noise at `point`, in roughly `[-1, 1]`.
+    pub fn noise(&self, point: Vec3) -> f32 {
+        let u = point.x - point.x.floor();
+        let v = point.y - point.y.floor();
+        let w = point.z - point.z.floor();
+
+        let i = point.x.floor() as i32;
+        let j = point.y.floor() as i32;
+        let k = point.z.floor() as i32;
+
+        let mut corners = [[[Vec3::ZERO; 2]; 2]; 2];
+        for (di, corner_i) in corners.iter_mut().enumerate() {
+            for (dj, corner_j) in corner_i.iter_mut().enumerate() {
+                for (dk, corner) in corner_j.iter_mut().enumerate() {
+                    let index = self.perm_x[((i + di as i32) & 255) as usize]
+                        ^ self.perm_y[((j + dj as i32) & 255) as usize]
+                        ^ self.perm_z[((k + dk as i32) & 255) as usize];
+                    *corner = self.random_vectors[index];
+                }
+            }
+        }
+
+        Self::interpolate(corners, u, v, w)
+    }
+
+    /// Noise summed over a few octaves at decreasing amplitude, for a turbulent look.
+    pub fn turbulence(&self, point: Vec3, octaves: u32) -> f32 {
+        let mut accum = 0.0;
+        let mut sample_point = point;
+        let mut weight = 1.0;
+        for _ in 0..octaves {
+            accum += weight * self.noise(sample_point);
+            weight *= 0.5;
+            sample_point *= 2.0;
+        }
+        accum.abs()
+    }
+
+    fn interpolate(corners: [[[Vec3; 2]; 2]; 2], u: f32, v: f32, w: f32) -> f32 {
+        let fade = |t: f32| t * t * (3.0 - 2.0 * t);
+        let (uu, vv, ww) = (fade(u), fade(v), fade(w));
+
+        let mut accum = 0.0;
+        for (i, corner_i) in corners.iter().enumerate() {
+            for (j, corner_j) in corner_i.iter().enumerate() {
+                for (k, corner) in corner_j.iter().enumerate() {
+                    let weight = Vec3::new(u - i as f32, v - j as f32, w - k as f32);
+                    accum += (i as f32 * uu + (1.0 - i as f32) * (1.0 - uu))
+                        * (j as f32 * vv + (1.0 - j as f32) * (1.0 - vv))
+                        * (k as f32 * ww + (1.0 - k as f32) * (1.0 - ww))
+                        * corner.dot(weight);
+                }
+            }
+        }
+        accum
+    }
+}
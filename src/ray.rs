@@ -0,0 +1,20 @@
+use std::ops::Deref;
+
+use bevy::prelude::*;
+
+#[derive(Clone, Copy)]
+pub struct TimedRay {
+    pub ray: Ray,
+    pub time: f32,
+    /// Sampled once per primary ray (see `render::trace`) and carried through every
+    /// bounce, so a dielectric's wavelength-dependent IOR stays consistent along a path.
+    pub wavelength_nm: f32,
+}
+
+impl Deref for TimedRay {
+    type Target = Ray;
+
+    fn deref(&self) -> &Ray {
+        &self.ray
+    }
+}
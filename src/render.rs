@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+
+use crate::hittable::Hittable;
+use crate::ray::TimedRay;
+use crate::scene::Scene;
+use crate::spectrum::{sample_wavelength, wavelength_to_rgb};
+
+const MAX_BOUNCES: u32 = 8;
+const MIN_T: f32 = 0.001;
+
+fn to_vec3(color: Color) -> Vec3 {
+    Vec3::new(color.r(), color.g(), color.b())
+}
+
+/// Traces one spectral sample for a pixel: samples a wavelength for the primary ray so
+/// dielectric dispersion varies sample to sample, bounces it through `scene`, then folds
+/// the resulting single-wavelength radiance back into RGB. Averaging many calls into
+/// `Scene::accumulation` converges to a dispersed image.
+pub fn trace(origin: Vec3, direction: Vec3, time: f32, scene: &Scene) -> Color {
+    let wavelength_nm = sample_wavelength();
+    let ray = TimedRay {
+        ray: Ray {
+            origin,
+            direction: direction.normalize(),
+        },
+        time,
+        wavelength_nm,
+    };
+
+    let radiance = bounce(&ray, scene, MAX_BOUNCES) * to_vec3(wavelength_to_rgb(wavelength_nm));
+    Color::rgb(radiance.x, radiance.y, radiance.z)
+}
+
+fn bounce(ray: &TimedRay, scene: &Scene, depth: u32) -> Vec3 {
+    if depth == 0 {
+        return Vec3::ZERO;
+    }
+
+    let Some(hit) = scene.hit(ray, MIN_T..f32::MAX) else {
+        return Vec3::ZERO;
+    };
+
+    let material = hit.material;
+    let unit_direction = ray.direction.normalize();
+    let scattered_direction = material.scatter(unit_direction, &hit, ray.wavelength_nm);
+    let scattered = TimedRay {
+        ray: Ray {
+            origin: hit.point,
+            direction: scattered_direction,
+        },
+        time: ray.time,
+        wavelength_nm: ray.wavelength_nm,
+    };
+
+    let emission = to_vec3(material.get_emission());
+    let albedo = to_vec3(material.albedo.sample(hit.point));
+    emission + albedo * bounce(&scattered, scene, depth - 1)
+}
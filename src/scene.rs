@@ -1,6 +1,10 @@
 use std::ops::Range;
 
+use crate::aabb::Aabb;
+use crate::bvh::Bvh;
 use crate::hittable::{HitRecord, Hittable};
+use crate::ray::TimedRay;
+use crate::texture::Texture;
 
 use bevy::{math::vec3, prelude::*};
 use bevy_inspector_egui::{
@@ -13,6 +17,8 @@ use bevy_inspector_egui::{
 pub enum Shape {
     Sphere(Sphere),
     Plane(Plane),
+    MovingSphere(MovingSphere),
+    Quad(Quad),
 }
 
 impl Default for Shape {
@@ -24,12 +30,23 @@ impl Default for Shape {
 impl Hittable for Shape {
     fn hit(
         &self,
-        ray: &bevy::prelude::Ray,
+        ray: &TimedRay,
         interval: std::ops::Range<f32>,
     ) -> Option<crate::hittable::HitRecord> {
         match self {
             Shape::Sphere(object) => object.hit(ray, interval),
             Shape::Plane(object) => object.hit(ray, interval),
+            Shape::MovingSphere(object) => object.hit(ray, interval),
+            Shape::Quad(object) => object.hit(ray, interval),
+        }
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        match self {
+            Shape::Sphere(object) => object.bounding_box(),
+            Shape::Plane(object) => object.bounding_box(),
+            Shape::MovingSphere(object) => object.bounding_box(),
+            Shape::Quad(object) => object.bounding_box(),
         }
     }
 }
@@ -37,12 +54,20 @@ impl Hittable for Shape {
 #[derive(Reflect, Resource)]
 #[reflect(Resource, Default)]
 pub struct Scene {
+    /// Edited directly by the inspector (including add/remove). `rebuild_bvh_on_change`
+    /// re-partitions the cached BVH automatically whenever `Scene` changes; traversal
+    /// also degrades to a miss rather than panicking if a stale index is ever read
+    /// in between.
     pub shapes: Vec<Shape>,
     pub accumulate: bool,
     #[reflect(ignore)]
     pub frame_index: i32,
     #[reflect(ignore)]
     pub accumulation: Vec<f32>,
+    #[reflect(ignore)]
+    bvh: Option<Bvh>,
+    #[reflect(ignore)]
+    unbounded: Vec<usize>,
 }
 
 impl Default for Scene {
@@ -52,7 +77,7 @@ impl Default for Scene {
                 center: vec3(0.0, 0.0, 0.0),
                 radius: 1.0,
                 material: Mat {
-                    albedo: Color::rgb(1.0, 0.0, 1.0),
+                    albedo: Texture::Solid(Color::rgb(1.0, 0.0, 1.0)),
                     roughness: 0.8,
                     ..Default::default()
                 },
@@ -61,7 +86,7 @@ impl Default for Scene {
                 center: vec3(2.0, 0.0, -1.0),
                 radius: 1.0,
                 material: Mat {
-                    albedo: Color::rgb(0.2, 0.7, 0.1),
+                    albedo: Texture::Solid(Color::rgb(0.2, 0.7, 0.1)),
                     roughness: 0.6,
                     ..Default::default()
                 },
@@ -70,7 +95,7 @@ impl Default for Scene {
                 center: vec3(0.0, -101.0, 0.0),
                 radius: 100.0,
                 material: Mat {
-                    albedo: Color::rgb(0.2, 0.3, 6.0),
+                    albedo: Texture::Solid(Color::rgb(0.2, 0.3, 6.0)),
                     roughness: 0.5,
                     ..Default::default()
                 },
@@ -95,35 +120,144 @@ impl Plugin for Scene {
             .register_type::<Mat>()
             .register_type::<Sphere>()
             .register_type::<Plane>()
-            .register_type::<Shape>();
+            .register_type::<MovingSphere>()
+            .register_type::<Quad>()
+            .register_type::<Texture>()
+            .register_type::<Shape>()
+            .add_systems(Update, rebuild_bvh_on_change);
+    }
+}
+
+/// Rebuilds the BVH whenever `Scene` changed this frame (e.g. an inspector-driven
+/// edit to `shapes`), so cached indices never stay stale across a frame boundary.
+/// Uses `bypass_change_detection` so the rebuild's own field writes don't mark the
+/// resource changed again and trigger a rebuild every subsequent frame.
+fn rebuild_bvh_on_change(mut scene: ResMut<Scene>) {
+    if scene.is_changed() && !scene.is_added() {
+        scene.bypass_change_detection().rebuild_bvh();
     }
 }
 
 impl Scene {
     pub fn new(shapes: Vec<Shape>) -> Self {
-        Self {
+        let mut scene = Self {
             shapes,
             accumulate: false,
             frame_index: 0,
             accumulation: vec![],
-        }
+            bvh: None,
+            unbounded: vec![],
+        };
+        scene.rebuild_bvh();
+        scene
     }
 
     pub fn resize(&mut self) {
         self.frame_index = -1;
+        self.rebuild_bvh();
+    }
+
+    /// Re-partitions `shapes` into the BVH and the unbounded list. `rebuild_bvh_on_change`
+    /// also runs this automatically once per frame after any edit to `self` (including an
+    /// inspector-driven add/remove of `shapes`), so manual calls are only needed for
+    /// edits that must take effect before the next frame.
+    pub fn rebuild_bvh(&mut self) {
+        let mut bounded = vec![];
+        self.unbounded.clear();
+        for (index, shape) in self.shapes.iter().enumerate() {
+            if shape.bounding_box().is_some() {
+                bounded.push(index);
+            } else {
+                self.unbounded.push(index);
+            }
+        }
+        self.bvh = Bvh::build(&self.shapes, &bounded);
+    }
+
+    /// The classic Cornell box: five walls plus a bright emissive quad light,
+    /// selectable as a scene preset to validate global-illumination behavior.
+    pub fn cornell_box() -> Self {
+        let red = Mat {
+            albedo: Texture::Solid(Color::rgb(0.65, 0.05, 0.05)),
+            ..Default::default()
+        };
+        let white = Mat {
+            albedo: Texture::Solid(Color::rgb(0.73, 0.73, 0.73)),
+            ..Default::default()
+        };
+        let green = Mat {
+            albedo: Texture::Solid(Color::rgb(0.12, 0.45, 0.15)),
+            ..Default::default()
+        };
+        let light = Mat {
+            emission: 15.0,
+            emission_color: Color::rgb(1.0, 1.0, 1.0),
+            ..Default::default()
+        };
+
+        Scene::new(vec![
+            Shape::Quad(Quad {
+                q: vec3(555.0, 0.0, 0.0),
+                u: vec3(0.0, 555.0, 0.0),
+                v: vec3(0.0, 0.0, 555.0),
+                material: green,
+            }),
+            Shape::Quad(Quad {
+                q: vec3(0.0, 0.0, 0.0),
+                u: vec3(0.0, 555.0, 0.0),
+                v: vec3(0.0, 0.0, 555.0),
+                material: red,
+            }),
+            Shape::Quad(Quad {
+                q: vec3(343.0, 554.0, 332.0),
+                u: vec3(-130.0, 0.0, 0.0),
+                v: vec3(0.0, 0.0, -105.0),
+                material: light,
+            }),
+            Shape::Quad(Quad {
+                q: vec3(0.0, 0.0, 0.0),
+                u: vec3(555.0, 0.0, 0.0),
+                v: vec3(0.0, 0.0, 555.0),
+                material: white,
+            }),
+            Shape::Quad(Quad {
+                q: vec3(555.0, 555.0, 555.0),
+                u: vec3(-555.0, 0.0, 0.0),
+                v: vec3(0.0, 0.0, -555.0),
+                material: white,
+            }),
+            Shape::Quad(Quad {
+                q: vec3(0.0, 0.0, 555.0),
+                u: vec3(555.0, 0.0, 0.0),
+                v: vec3(0.0, 555.0, 0.0),
+                material: white,
+            }),
+        ])
     }
 }
 
 impl Hittable for Scene {
-    fn hit(&self, ray: &Ray, interval: Range<f32>) -> Option<HitRecord> {
-        self.shapes.hit(ray, interval)
+    fn hit(&self, ray: &TimedRay, interval: Range<f32>) -> Option<HitRecord> {
+        let mut result = self
+            .bvh
+            .as_ref()
+            .and_then(|bvh| bvh.hit(&self.shapes, ray, interval.clone()));
+        for &index in &self.unbounded {
+            let closest = result.as_ref().map_or(interval.end, |hit| hit.t);
+            if let Some(shape) = self.shapes.get(index) {
+                if let Some(hit) = shape.hit(ray, interval.start..closest) {
+                    result = Some(hit);
+                }
+            }
+        }
+        result
     }
 }
 
-#[derive(Reflect, InspectorOptions, Default, Clone, Copy)]
+#[derive(Reflect, InspectorOptions, Clone, Copy)]
 #[reflect(Default, InspectorOptions)]
 pub struct Mat {
-    pub albedo: Color,
+    pub albedo: Texture,
     #[inspector(min = 0.0, max = 1.0, display = NumberDisplay::Slider)]
     pub roughness: f32,
     #[inspector(min = 0.0, max = f32::MAX, display = NumberDisplay::Drag)]
@@ -131,11 +265,95 @@ pub struct Mat {
     pub emission_color: Color,
     #[inspector(min = 0.0, max = 1.0, display = NumberDisplay::Slider)]
     pub specular_chance: f32,
+    #[inspector(min = 0.0, max = 1.0, display = NumberDisplay::Slider)]
+    pub transparency: f32,
+    #[inspector(min = 1.0, max = 3.0, display = NumberDisplay::Drag)]
+    pub cauchy_a: f32,
+    #[inspector(min = 0.0, max = 0.1, display = NumberDisplay::Drag)]
+    pub cauchy_b: f32,
+}
+
+impl Default for Mat {
+    fn default() -> Self {
+        Self {
+            albedo: Texture::default(),
+            roughness: 0.0,
+            emission: 0.0,
+            emission_color: Color::default(),
+            specular_chance: 0.0,
+            transparency: 0.0,
+            cauchy_a: 1.5,
+            cauchy_b: 0.004,
+        }
+    }
 }
+
 impl Mat {
     pub fn get_emission(&self) -> Color {
         self.emission_color * self.emission
     }
+
+    /// Index of refraction at a given wavelength via Cauchy's equation, `cauchy_b`
+    /// expressed for `wavelength_nm` in micrometers.
+    pub fn ior_at(&self, wavelength_nm: f32) -> f32 {
+        let micrometers = wavelength_nm * 0.001;
+        self.cauchy_a + self.cauchy_b / (micrometers * micrometers)
+    }
+
+    /// Refracts (or reflects, on total internal reflection / a Schlick sample)
+    /// `unit_direction` through a dielectric boundary at `wavelength_nm`.
+    pub fn refract(&self, unit_direction: Vec3, normal: Vec3, front_face: bool, wavelength_nm: f32) -> Vec3 {
+        let normal = if front_face { normal } else { -normal };
+        let ior = self.ior_at(wavelength_nm);
+        let ratio = if front_face { 1.0 / ior } else { ior };
+        let cos_theta = (-unit_direction).dot(normal).min(1.0);
+        let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
+
+        if ratio * sin_theta > 1.0 || schlick_reflectance(cos_theta, ratio) > rand::random::<f32>() {
+            return reflect(unit_direction, normal);
+        }
+
+        let perpendicular = ratio * (unit_direction + cos_theta * normal);
+        let parallel = -(1.0 - perpendicular.length_squared()).abs().sqrt() * normal;
+        perpendicular + parallel
+    }
+
+    /// Picks this material's scatter kind for one bounce: dielectric refraction
+    /// (weighted by `transparency`), then mirror reflection (weighted by
+    /// `specular_chance`), falling back to Lambertian diffuse.
+    pub fn scatter(&self, unit_direction: Vec3, hit: &HitRecord, wavelength_nm: f32) -> Vec3 {
+        if self.transparency > rand::random::<f32>() {
+            return self.refract(unit_direction, hit.normal, hit.front_face, wavelength_nm);
+        }
+        if self.specular_chance > rand::random::<f32>() {
+            return reflect(unit_direction, hit.normal);
+        }
+        let normal = if hit.front_face { hit.normal } else { -hit.normal };
+        normal + random_unit_vector()
+    }
+}
+
+fn schlick_reflectance(cos_theta: f32, ratio: f32) -> f32 {
+    let r0 = ((1.0 - ratio) / (1.0 + ratio)).powi(2);
+    r0 + (1.0 - r0) * (1.0 - cos_theta).powi(5)
+}
+
+fn reflect(direction: Vec3, normal: Vec3) -> Vec3 {
+    direction - 2.0 * direction.dot(normal) * normal
+}
+
+fn random_unit_vector() -> Vec3 {
+    loop {
+        let candidate = Vec3::new(
+            rand::random::<f32>() * 2.0 - 1.0,
+            rand::random::<f32>() * 2.0 - 1.0,
+            rand::random::<f32>() * 2.0 - 1.0,
+        );
+        let length_sq = candidate.length_squared();
+        if length_sq > 1e-12 && length_sq <= 1.0 {
+            return candidate.normalize();
+        }
+    }
 }
 
 #[derive(Reflect, Default)]
@@ -147,7 +365,7 @@ pub struct Sphere {
 }
 
 impl Hittable for Sphere {
-    fn hit(&self, ray: &Ray, interval: Range<f32>) -> Option<HitRecord> {
+    fn hit(&self, ray: &TimedRay, interval: Range<f32>) -> Option<HitRecord> {
         let origin = ray.origin - self.center;
         let a = ray.direction.dot(ray.direction);
         let b = origin.dot(ray.direction);
@@ -163,15 +381,25 @@ impl Hittable for Sphere {
         }
         let hit = origin + ray.direction * t;
         let normal = hit.normalize();
+        let front_face = ray.direction.dot(normal) < 0.0;
         let point = hit + self.center;
         let material = self.material;
         Some(HitRecord {
             point,
             normal,
             t,
+            front_face,
             material,
         })
     }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let radius = vec3(self.radius, self.radius, self.radius);
+        Some(Aabb {
+            min: self.center - radius,
+            max: self.center + radius,
+        })
+    }
 }
 
 #[derive(Reflect, Default)]
@@ -183,13 +411,136 @@ pub struct Plane {
 }
 
 impl Hittable for Plane {
-    fn hit(&self, ray: &Ray, interval: Range<f32>) -> Option<HitRecord> {
+    fn hit(&self, ray: &TimedRay, interval: Range<f32>) -> Option<HitRecord> {
         let t = self.normal.dot(self.point - ray.origin) / self.normal.dot(ray.direction);
         (interval.contains(&t)).then_some(HitRecord {
             point: ray.origin + ray.direction * t,
             normal: self.normal,
             t,
+            front_face: ray.direction.dot(self.normal) < 0.0,
             material: self.material,
         })
     }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        None
+    }
+}
+
+#[derive(Reflect, Default)]
+#[reflect(Default)]
+pub struct MovingSphere {
+    pub center0: Vec3,
+    pub center1: Vec3,
+    pub time0: f32,
+    pub time1: f32,
+    pub radius: f32,
+    pub material: Mat,
+}
+
+impl MovingSphere {
+    pub fn center_at(&self, time: f32) -> Vec3 {
+        self.center0
+            + ((time - self.time0) / (self.time1 - self.time0)) * (self.center1 - self.center0)
+    }
+}
+
+impl Hittable for MovingSphere {
+    fn hit(&self, ray: &TimedRay, interval: Range<f32>) -> Option<HitRecord> {
+        let center = self.center_at(ray.time);
+        let origin = ray.origin - center;
+        let a = ray.direction.dot(ray.direction);
+        let b = origin.dot(ray.direction);
+        let c = origin.dot(origin) - self.radius * self.radius;
+
+        let dis = b * b - a * c;
+        if dis < 0.0 {
+            return None;
+        }
+        let t = (-b - dis.sqrt()) / a;
+        if !interval.contains(&t) {
+            return None;
+        }
+        let hit = origin + ray.direction * t;
+        let normal = hit.normalize();
+        let front_face = ray.direction.dot(normal) < 0.0;
+        let point = hit + center;
+        let material = self.material;
+        Some(HitRecord {
+            point,
+            normal,
+            t,
+            front_face,
+            material,
+        })
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let radius = vec3(self.radius, self.radius, self.radius);
+        let box0 = Aabb {
+            min: self.center0 - radius,
+            max: self.center0 + radius,
+        };
+        let box1 = Aabb {
+            min: self.center1 - radius,
+            max: self.center1 + radius,
+        };
+        Some(Aabb::surrounding(box0, box1))
+    }
+}
+
+#[derive(Reflect, Default)]
+#[reflect(Default)]
+pub struct Quad {
+    pub q: Vec3,
+    pub u: Vec3,
+    pub v: Vec3,
+    pub material: Mat,
+}
+
+impl Hittable for Quad {
+    fn hit(&self, ray: &TimedRay, interval: Range<f32>) -> Option<HitRecord> {
+        let n = self.u.cross(self.v);
+        let normal = n.normalize();
+        let d = normal.dot(self.q);
+        let denom = normal.dot(ray.direction);
+        if denom.abs() < 1e-8 {
+            return None;
+        }
+
+        let t = (d - normal.dot(ray.origin)) / denom;
+        if !interval.contains(&t) {
+            return None;
+        }
+
+        let point = ray.origin + ray.direction * t;
+        let planar = point - self.q;
+        let w = n / n.dot(n);
+        let alpha = w.dot(planar.cross(self.v));
+        let beta = w.dot(self.u.cross(planar));
+        if !(0.0..=1.0).contains(&alpha) || !(0.0..=1.0).contains(&beta) {
+            return None;
+        }
+
+        Some(HitRecord {
+            point,
+            normal,
+            t,
+            front_face: ray.direction.dot(normal) < 0.0,
+            material: self.material,
+        })
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let opposite = self.q + self.u + self.v;
+        let diagonal1 = Aabb {
+            min: self.q.min(opposite),
+            max: self.q.max(opposite),
+        };
+        let diagonal2 = Aabb {
+            min: (self.q + self.u).min(self.q + self.v),
+            max: (self.q + self.u).max(self.q + self.v),
+        };
+        Some(Aabb::surrounding(diagonal1, diagonal2).padded())
+    }
 }
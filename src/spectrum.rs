@@ -0,0 +1,24 @@
+use std::ops::Range;
+
+use bevy::prelude::Color;
+
+pub const VISIBLE_RANGE: Range<f32> = 380.0..750.0;
+
+pub fn sample_wavelength() -> f32 {
+    VISIBLE_RANGE.start + rand::random::<f32>() * (VISIBLE_RANGE.end - VISIBLE_RANGE.start)
+}
+
+/// Approximate CIE color-matching weight for a single wavelength, used to fold a
+/// spectral radiance sample back into RGB (a piecewise-Gaussian fit to the visible spectrum).
+pub fn wavelength_to_rgb(wavelength_nm: f32) -> Color {
+    fn gauss(x: f32, mu: f32, sigma_lo: f32, sigma_hi: f32) -> f32 {
+        let sigma = if x < mu { sigma_lo } else { sigma_hi };
+        (-0.5 * ((x - mu) / sigma).powi(2)).exp()
+    }
+
+    let r = gauss(wavelength_nm, 599.8, 37.9, 31.0) + 0.363 * gauss(wavelength_nm, 446.8, 19.4, 27.1);
+    let g = gauss(wavelength_nm, 539.1, 46.0, 33.1);
+    let b = gauss(wavelength_nm, 467.0, 27.8, 33.3);
+
+    Color::rgb(r.clamp(0.0, 1.0), g.clamp(0.0, 1.0), b.clamp(0.0, 1.0))
+}
@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use crate::perlin;
+
+#[derive(Reflect, Clone, Copy)]
+#[reflect(Default)]
+pub enum Texture {
+    Solid(Color),
+    Checker { even: Color, odd: Color, scale: f32 },
+    Noise { scale: f32, base: Color },
+}
+
+impl Default for Texture {
+    fn default() -> Self {
+        Self::Solid(Color::default())
+    }
+}
+
+impl Texture {
+    pub fn sample(&self, point: Vec3) -> Color {
+        match *self {
+            Texture::Solid(color) => color,
+            Texture::Checker { even, odd, scale } => {
+                let sign = (scale * point.x).floor() as i64
+                    + (scale * point.y).floor() as i64
+                    + (scale * point.z).floor() as i64;
+                if sign.rem_euclid(2) == 0 {
+                    even
+                } else {
+                    odd
+                }
+            }
+            Texture::Noise { scale, base } => base * perlin::lattice().turbulence(point * scale, 7),
+        }
+    }
+}